@@ -0,0 +1,167 @@
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::git;
+use crate::graph::{FileTrie, ReverseDepGraph};
+use crate::lock::VpmLock;
+use crate::source;
+use crate::workspace;
+
+/// Computes the minimal set of `top_modules` that `sim`/`synth`/`run` need to rebuild, by
+/// diffing tracked file hashes (relative to `root`, the dependency's checkout directory)
+/// against what's recorded in `vpm.lock` and walking the reverse dependency graph from
+/// there. A deleted file counts as changed so its dependents still get invalidated.
+pub fn affected_top_modules(
+    repo_link: &str,
+    root: &Path,
+    top_modules: &[String],
+    file_trie: &FileTrie,
+    graph: &ReverseDepGraph,
+    lock: &VpmLock,
+) -> Result<Vec<String>> {
+    let mut changed_modules = HashSet::new();
+
+    for file_path in file_trie.file_paths() {
+        let previous_hash = lock.get_file_hash(repo_link, &file_path);
+        let current_hash = fs::read(root.join(&file_path))
+            .ok()
+            .map(|contents| hash_contents(&contents));
+
+        if previous_hash != current_hash.as_deref() {
+            changed_modules.extend(file_trie.modules_at(&file_path).iter().cloned());
+        }
+    }
+
+    let changed_modules: Vec<String> = changed_modules.into_iter().collect();
+    let affected = graph.affected(&changed_modules);
+
+    Ok(top_modules
+        .iter()
+        .filter(|m| affected.contains(*m))
+        .cloned()
+        .collect())
+}
+
+pub fn hash_contents(contents: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(contents);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Hashes every file in `files` (paths under `root`), returning `(relative path, hash)`
+/// pairs ready for `VpmLock::set_package` to persist.
+pub fn hash_files(root: &Path, files: &[PathBuf]) -> Result<Vec<(String, String)>> {
+    files
+        .iter()
+        .map(|file| {
+            let relative = file
+                .strip_prefix(root)
+                .unwrap_or(file)
+                .to_string_lossy()
+                .replace('\\', "/");
+            let hash = hash_contents(&fs::read(file)?);
+            Ok((relative, hash))
+        })
+        .collect()
+}
+
+/// The minimal set of top modules `sim`/`synth`/`run` need to process this invocation,
+/// across every dependency (deduplicated across workspace members, if any). Each
+/// dependency's sources are read from its already-fetched checkout directory.
+pub fn modules_to_rebuild() -> Result<Vec<String>> {
+    let dependencies = workspace::effective_dependencies(&std::env::current_dir()?)?;
+    let lock = VpmLock::from("vpm.lock");
+
+    let mut modules = Vec::new();
+    for (repo_link, top_modules) in &dependencies {
+        let root = git::checkout_dir(repo_link);
+        let files = source::discover_files(&root)?;
+        let (file_trie, graph) = source::build_graph(&root, &files)?;
+
+        modules.extend(affected_top_modules(
+            repo_link,
+            &root,
+            top_modules,
+            &file_trie,
+            &graph,
+            &lock,
+        )?);
+    }
+    Ok(modules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// End-to-end exercise of the pipeline `install` feeds into `sim`/`synth`: real files on
+    /// disk, discovered and parsed by `source`, diffed against a `VpmLock` the way
+    /// `modules_to_rebuild` does, with a change in an included file propagating to the
+    /// top module that includes it.
+    #[test]
+    fn affected_top_modules_propagates_through_include_edges() {
+        let root = std::env::temp_dir().join(format!(
+            "vpm_changes_affected_top_modules_{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+
+        fs::write(root.join("leaf.v"), "module leaf;\nendmodule\n").unwrap();
+        fs::write(
+            root.join("top.v"),
+            "`include \"leaf.v\"\nmodule top;\nendmodule\n",
+        )
+        .unwrap();
+
+        let files = source::discover_files(&root).unwrap();
+        let (file_trie, graph) = source::build_graph(&root, &files).unwrap();
+        let file_hashes = hash_files(&root, &files).unwrap();
+
+        let lock_path = format!(
+            "vpm_changes_affected_top_modules_{}.lock",
+            std::process::id()
+        );
+        let mut lock = VpmLock::from(&lock_path);
+        let repo_link = "example.com/repo";
+        lock.set_package(
+            repo_link,
+            "abc123",
+            &["leaf".to_string(), "top".to_string()],
+            &file_hashes,
+        );
+
+        // Nothing changed yet: no top module should be flagged.
+        let affected = affected_top_modules(
+            repo_link,
+            &root,
+            &["leaf".to_string(), "top".to_string()],
+            &file_trie,
+            &graph,
+            &lock,
+        )
+        .unwrap();
+        assert!(affected.is_empty());
+
+        // Editing the leaf should invalidate both `leaf` (defined there) and `top`
+        // (which `include`s it), but nothing unrelated.
+        fs::write(root.join("leaf.v"), "module leaf;\n// changed\nendmodule\n").unwrap();
+        let mut affected = affected_top_modules(
+            repo_link,
+            &root,
+            &["leaf".to_string(), "top".to_string()],
+            &file_trie,
+            &graph,
+            &lock,
+        )
+        .unwrap();
+        affected.sort();
+        assert_eq!(affected, vec!["leaf".to_string(), "top".to_string()]);
+
+        let _ = fs::remove_dir_all(&root);
+        let _ = fs::remove_file(&lock_path);
+    }
+}
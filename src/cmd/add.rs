@@ -0,0 +1,144 @@
+use anyhow::{bail, Result};
+use clap::Args;
+use semver::VersionReq;
+
+use crate::cmd::Execute;
+use crate::git;
+use crate::toml::{self, GitReference};
+use crate::version;
+
+/// `vpm add <url>[@<version>] [--rev <sha> | --branch <name> | --tag <name>] [--module <name>]... [--rename <alias>]`
+#[derive(Args, Debug)]
+pub struct Add {
+    /// Git URL of the dependency, optionally suffixed with `@<version req>` (e.g. `github.com/user/repo@^1.2`).
+    repo: String,
+
+    /// Pin to an exact commit.
+    #[arg(long)]
+    rev: Option<String>,
+
+    /// Track a branch; `update` re-resolves its HEAD.
+    #[arg(long)]
+    branch: Option<String>,
+
+    /// Pin to a tag.
+    #[arg(long)]
+    tag: Option<String>,
+
+    /// A top module to pull in from this dependency. Repeatable.
+    #[arg(long = "module")]
+    modules: Vec<String>,
+
+    /// Reference this dependency locally under a different name.
+    #[arg(long)]
+    rename: Option<String>,
+}
+
+impl Add {
+    fn parse_repo_spec(&self) -> Result<(String, Option<VersionReq>)> {
+        // Split on the *last* `@`: SSH-style URLs (`git@github.com:user/repo.git`) have one
+        // before the host, so splitting on the first `@` would cut the URL in half. If
+        // what follows doesn't parse as a version requirement, fall back to treating the
+        // whole spec as a bare URL rather than erroring on an SSH login that contains '@'.
+        if let Some((git, version)) = self.repo.rsplit_once('@') {
+            if let Ok(version_req) = VersionReq::parse(version) {
+                return Ok((git.to_string(), Some(version_req)));
+            }
+        }
+        Ok((self.repo.clone(), None))
+    }
+
+    fn git_reference(&self) -> Result<Option<GitReference>> {
+        match (&self.branch, &self.tag, &self.rev) {
+            (Some(branch), None, None) => Ok(Some(GitReference::Branch(branch.clone()))),
+            (None, Some(tag), None) => Ok(Some(GitReference::Tag(tag.clone()))),
+            (None, None, Some(rev)) => Ok(Some(GitReference::Rev(rev.clone()))),
+            (None, None, None) => Ok(None),
+            _ => bail!("only one of --branch, --tag, --rev may be given"),
+        }
+    }
+}
+
+impl Execute for Add {
+    async fn execute(&self) -> Result<()> {
+        let (git, version) = self.parse_repo_spec()?;
+        let git_ref = self.git_reference()?;
+
+        if version.is_some() && git_ref.is_some() {
+            bail!("cannot combine a `@<version>` requirement with --branch/--tag/--rev; pick one");
+        }
+
+        let commit = match (&version, &git_ref) {
+            (Some(version_req), _) => {
+                let tags = git::list_tags(&git)?;
+                let (tag, _resolved_version) = version::resolve_version(&tags, version_req)?;
+                Some(git::resolve_ref(&git, &GitReference::Tag(tag))?)
+            }
+            // A `--rev` is already a concrete commit; no resolution needed.
+            (None, Some(GitReference::Rev(rev))) => Some(rev.clone()),
+            (None, _) => None,
+        };
+
+        toml::add_dependency(&git, git_ref, version.as_ref(), commit.as_deref())?;
+
+        if let Some(alias) = &self.rename {
+            toml::set_dependency_alias(&git, alias)?;
+        }
+
+        for module in &self.modules {
+            toml::add_top_module(&git, module)?;
+        }
+
+        match self.rename.as_deref() {
+            Some(alias) => println!("Added {git} as `{alias}` with modules {:?}", self.modules),
+            None => println!("Added {git} with modules {:?}", self.modules),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add(repo: &str, branch: Option<&str>, tag: Option<&str>, rev: Option<&str>) -> Add {
+        Add {
+            repo: repo.to_string(),
+            rev: rev.map(str::to_string),
+            branch: branch.map(str::to_string),
+            tag: tag.map(str::to_string),
+            modules: Vec::new(),
+            rename: None,
+        }
+    }
+
+    #[test]
+    fn parse_repo_spec_splits_off_a_trailing_version_requirement() {
+        let (git, version) = add("example.com/repo@^1.2", None, None, None)
+            .parse_repo_spec()
+            .unwrap();
+        assert_eq!(git, "example.com/repo");
+        assert_eq!(version, Some(VersionReq::parse("^1.2").unwrap()));
+    }
+
+    #[test]
+    fn parse_repo_spec_treats_an_ssh_login_without_a_version_as_a_bare_url() {
+        let (git, version) = add("git@github.com:user/repo.git", None, None, None)
+            .parse_repo_spec()
+            .unwrap();
+        assert_eq!(git, "git@github.com:user/repo.git");
+        assert_eq!(version, None);
+    }
+
+    #[test]
+    fn git_reference_rejects_more_than_one_of_branch_tag_rev() {
+        assert!(add("example.com/repo", Some("main"), Some("v1.0.0"), None)
+            .git_reference()
+            .is_err());
+    }
+
+    #[test]
+    fn git_reference_is_none_when_nothing_given() {
+        assert_eq!(add("example.com/repo", None, None, None).git_reference().unwrap(), None);
+    }
+}
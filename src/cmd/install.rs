@@ -0,0 +1,86 @@
+use anyhow::{bail, Result};
+use clap::Args;
+use std::env;
+
+use crate::changes;
+use crate::cmd::Execute;
+use crate::git;
+use crate::lock::VpmLock;
+use crate::source;
+use crate::toml::{GitReference, VpmToml};
+use crate::version;
+use crate::workspace;
+
+/// `vpm install [--locked]`
+#[derive(Args, Debug)]
+pub struct Install {
+    /// Fail instead of touching `vpm.lock` if it's missing or out of date with `vpm.toml`.
+    #[arg(long)]
+    locked: bool,
+}
+
+impl Execute for Install {
+    async fn execute(&self) -> Result<()> {
+        let cwd = env::current_dir()?;
+        // Dependencies to satisfy: the deduplicated union across workspace members if
+        // we're in one, otherwise just the current directory's `vpm.toml`.
+        let dependencies = workspace::effective_dependencies(&cwd)?;
+        if self.locked && !std::path::Path::new("vpm.lock").exists() {
+            bail!("vpm.lock is missing (run `vpm install` without --locked to create it)");
+        }
+        let mut lock = VpmLock::from("vpm.lock");
+
+        let stale = lock.stale_dependencies(&dependencies);
+
+        if stale.is_empty() {
+            println!("vpm.lock is up to date.");
+            return Ok(());
+        }
+
+        if self.locked {
+            bail!(
+                "vpm.lock is out of date for: {} (run `vpm install` without --locked to update it)",
+                stale.join(", ")
+            );
+        }
+
+        for repo_link in &stale {
+            // Resolve and pin against the manifest that actually declared this dependency
+            // (a workspace member, or the plain current-directory `vpm.toml`) rather than
+            // whatever manifest happens to sit in the current directory.
+            let manifest_path = workspace::manifest_path_for(&cwd, repo_link)?;
+            let mut owning_toml = VpmToml::from(&manifest_path.to_string_lossy());
+
+            let commit = resolve_commit(&owning_toml, repo_link)?;
+            owning_toml.set_resolved_commit(repo_link, &commit);
+            owning_toml.write_to_file(&manifest_path.to_string_lossy())?;
+
+            let top_modules = &dependencies[repo_link];
+            // Fetch the pinned commit's sources so change detection has real file hashes
+            // to diff against on the next `sim`/`synth`/`run`.
+            let checkout = git::fetch_commit(repo_link, &commit)?;
+            let files = source::discover_files(&checkout)?;
+            let file_hashes = changes::hash_files(&checkout, &files)?;
+            lock.set_package(repo_link, &commit, top_modules, &file_hashes);
+            println!("Resolved {repo_link} -> {commit}");
+        }
+
+        lock.write_to_file("vpm.lock")?;
+        Ok(())
+    }
+}
+
+/// Resolves a dependency's pin to a concrete commit: a `version` requirement is resolved
+/// against the repo's tags first, falling back to a direct `branch`/`tag`/`rev` pin.
+fn resolve_commit(vpm_toml: &VpmToml, repo_link: &str) -> Result<String> {
+    if let Some(version_req) = vpm_toml.get_version_req(repo_link)? {
+        let tags = git::list_tags(repo_link)?;
+        let (tag, _resolved_version) = version::resolve_version(&tags, &version_req)?;
+        return git::resolve_ref(repo_link, &GitReference::Tag(tag));
+    }
+
+    match vpm_toml.get_git_reference(repo_link)? {
+        Some(git_ref) => git::resolve_ref(repo_link, &git_ref),
+        None => bail!("dependency `{repo_link}` has no branch/tag/rev/version to resolve"),
+    }
+}
@@ -0,0 +1,21 @@
+use anyhow::Result;
+use clap::Args;
+use std::env;
+
+use crate::cmd::Execute;
+use crate::workspace;
+
+/// `vpm list` — lists every dependency's top modules, deduplicated across workspace
+/// members if run from inside one.
+#[derive(Args, Debug)]
+pub struct List {}
+
+impl Execute for List {
+    async fn execute(&self) -> Result<()> {
+        let dependencies = workspace::effective_dependencies(&env::current_dir()?)?;
+        for (repo_link, top_modules) in &dependencies {
+            println!("{repo_link}: {}", top_modules.join(", "));
+        }
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+mod add;
 #[allow(clippy::module_inception)]
 mod cmd;
 mod docs;
@@ -24,6 +25,7 @@ pub trait Execute {
 impl Execute for Cmd {
     async fn execute(&self) -> Result<()> {
         match self {
+            Cmd::Add(cmd) => cmd.execute().await,
             Cmd::Upgrade(cmd) => cmd.execute().await,
             Cmd::Include(cmd) => cmd.execute().await,
             Cmd::Update(cmd) => cmd.execute().await,
@@ -0,0 +1,22 @@
+use anyhow::Result;
+use clap::Args;
+
+use crate::changes;
+use crate::cmd::Execute;
+
+/// `vpm run` — runs only the top modules affected by changes since `install` last
+/// recorded their file hashes in `vpm.lock`.
+#[derive(Args, Debug)]
+pub struct Run {}
+
+impl Execute for Run {
+    async fn execute(&self) -> Result<()> {
+        let modules = changes::modules_to_rebuild()?;
+        if modules.is_empty() {
+            println!("Nothing changed, nothing to run.");
+            return Ok(());
+        }
+        println!("Running: {}", modules.join(", "));
+        Ok(())
+    }
+}
@@ -0,0 +1,22 @@
+use anyhow::Result;
+use clap::Args;
+
+use crate::changes;
+use crate::cmd::Execute;
+
+/// `vpm synth` — synthesizes only the top modules affected by changes since `install` last
+/// recorded their file hashes in `vpm.lock`.
+#[derive(Args, Debug)]
+pub struct Synth {}
+
+impl Execute for Synth {
+    async fn execute(&self) -> Result<()> {
+        let modules = changes::modules_to_rebuild()?;
+        if modules.is_empty() {
+            println!("Nothing changed, nothing to synthesize.");
+            return Ok(());
+        }
+        println!("Synthesizing: {}", modules.join(", "));
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use anyhow::Result;
+use clap::Args;
+
+use crate::cmd::Execute;
+use crate::git;
+use crate::toml::{GitReference, VpmToml};
+use crate::version;
+
+/// `vpm update` — re-resolves every branch-pinned dependency to its current HEAD commit,
+/// and every version-pinned dependency to the newest tag still matching its requirement.
+/// Tag and rev pins are left untouched since they're meant to stay put.
+#[derive(Args, Debug)]
+pub struct Update {}
+
+impl Execute for Update {
+    async fn execute(&self) -> Result<()> {
+        let mut vpm_toml = VpmToml::from("vpm.toml");
+        let Some(dependencies) = vpm_toml.get_dependencies() else {
+            return Ok(());
+        };
+        let repo_links: Vec<String> = dependencies.iter().map(|(link, _)| link.to_string()).collect();
+
+        for repo_link in repo_links {
+            if let Some(version_req) = vpm_toml.get_version_req(&repo_link)? {
+                let tags = git::list_tags(&repo_link)?;
+                let (tag, resolved_version) = version::resolve_version(&tags, &version_req)?;
+                let commit = git::resolve_ref(&repo_link, &GitReference::Tag(tag))?;
+                vpm_toml.set_resolved_commit(&repo_link, &commit);
+                println!("Updated {repo_link} (version `{version_req}`) -> {resolved_version} ({commit})");
+                continue;
+            }
+
+            let Some(git_ref @ GitReference::Branch(ref branch)) =
+                vpm_toml.get_git_reference(&repo_link)?
+            else {
+                continue;
+            };
+            let commit = git::resolve_ref(&repo_link, &git_ref)?;
+            vpm_toml.set_resolved_commit(&repo_link, &commit);
+            println!("Updated {repo_link} (branch `{branch}`) -> {commit}");
+        }
+
+        vpm_toml.write_to_file("vpm.toml")?;
+        Ok(())
+    }
+}
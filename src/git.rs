@@ -0,0 +1,93 @@
+use anyhow::{bail, Result};
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::toml::GitReference;
+
+/// Where a dependency's sources are checked out to on disk, keyed by its git URL.
+pub fn checkout_dir(repo_url: &str) -> PathBuf {
+    let sanitized: String = repo_url
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    PathBuf::from(".vpm").join(sanitized)
+}
+
+/// Clones `repo_url` into its checkout dir (if not already present) and checks out
+/// `commit`, returning that directory so callers can walk its sources.
+pub fn fetch_commit(repo_url: &str, commit: &str) -> Result<PathBuf> {
+    let dest = checkout_dir(repo_url);
+
+    if !dest.exists() {
+        let status = Command::new("git")
+            .args(["clone", repo_url, &dest.to_string_lossy()])
+            .status()?;
+        if !status.success() {
+            bail!("git clone {repo_url} into {} failed", dest.display());
+        }
+    }
+
+    let status = Command::new("git")
+        .args(["-C", &dest.to_string_lossy(), "checkout", commit])
+        .status()?;
+    if !status.success() {
+        bail!(
+            "git -C {} checkout {commit} failed",
+            dest.display()
+        );
+    }
+
+    Ok(dest)
+}
+
+/// Resolves a `GitReference` against a remote repo to a concrete commit SHA. `Rev` is
+/// already concrete and is returned as-is; `Branch`/`Tag` are resolved via `git ls-remote`,
+/// the same way `update` later re-resolves a branch's moving HEAD.
+pub fn resolve_ref(repo_url: &str, git_ref: &GitReference) -> Result<String> {
+    match git_ref {
+        GitReference::Rev(rev) => Ok(rev.clone()),
+        GitReference::Branch(branch) => resolve_symbolic_ref(repo_url, &format!("refs/heads/{branch}")),
+        GitReference::Tag(tag) => resolve_symbolic_ref(repo_url, &format!("refs/tags/{tag}")),
+    }
+}
+
+fn resolve_symbolic_ref(repo_url: &str, refname: &str) -> Result<String> {
+    let output = Command::new("git")
+        .args(["ls-remote", repo_url, refname])
+        .output()?;
+    if !output.status.success() {
+        bail!(
+            "git ls-remote {repo_url} {refname} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().next())
+        .map(str::to_string)
+        .ok_or_else(|| anyhow::anyhow!("ref `{refname}` not found in {repo_url}"))
+}
+
+/// Lists tag names (without the `refs/tags/` prefix) published by a repo, for resolving
+/// semver `version` requirements against.
+pub fn list_tags(repo_url: &str) -> Result<Vec<String>> {
+    let output = Command::new("git")
+        .args(["ls-remote", "--tags", repo_url])
+        .output()?;
+    if !output.status.success() {
+        bail!(
+            "git ls-remote --tags {repo_url} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .filter_map(|refname| refname.strip_prefix("refs/tags/"))
+        .filter(|tag| !tag.ends_with("^{}"))
+        .map(str::to_string)
+        .collect())
+}
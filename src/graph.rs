@@ -0,0 +1,169 @@
+use std::collections::{HashMap, HashSet};
+
+/// A trie over source file paths (split on `/`), where each leaf records the module(s)
+/// defined in that file. Lets change detection map a touched file straight to its modules
+/// without scanning the whole dependency tree.
+#[derive(Debug, Default)]
+pub struct FileTrie {
+    root: TrieNode,
+}
+
+#[derive(Debug, Default)]
+struct TrieNode {
+    children: HashMap<String, TrieNode>,
+    modules: Vec<String>,
+}
+
+impl FileTrie {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, file_path: &str, module: &str) {
+        let mut node = &mut self.root;
+        for part in file_path.split('/') {
+            node = node.children.entry(part.to_string()).or_default();
+        }
+        if !node.modules.iter().any(|m| m == module) {
+            node.modules.push(module.to_string());
+        }
+    }
+
+    pub fn modules_at(&self, file_path: &str) -> &[String] {
+        let mut node = &self.root;
+        for part in file_path.split('/') {
+            match node.children.get(part) {
+                Some(child) => node = child,
+                None => return &[],
+            }
+        }
+        &node.modules
+    }
+
+    pub fn file_paths(&self) -> Vec<String> {
+        let mut paths = Vec::new();
+        self.root.collect_paths(String::new(), &mut paths);
+        paths
+    }
+}
+
+impl TrieNode {
+    fn collect_paths(&self, prefix: String, out: &mut Vec<String>) {
+        if !self.modules.is_empty() {
+            out.push(prefix.clone());
+        }
+        for (part, child) in &self.children {
+            let child_prefix = if prefix.is_empty() {
+                part.clone()
+            } else {
+                format!("{prefix}/{part}")
+            };
+            child.collect_paths(child_prefix, out);
+        }
+    }
+}
+
+/// Reverse module dependency graph: maps a module to the modules that directly include it;
+/// `affected` follows these edges transitively. Built from each file's `include`
+/// relationships as modules are discovered.
+#[derive(Debug, Default)]
+pub struct ReverseDepGraph {
+    dependents: HashMap<String, Vec<String>>,
+}
+
+impl ReverseDepGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that `including_module` includes `included_module`, i.e. a change to
+    /// `included_module` must also invalidate `including_module`.
+    pub fn add_edge(&mut self, included_module: &str, including_module: &str) {
+        let dependents = self
+            .dependents
+            .entry(included_module.to_string())
+            .or_default();
+        if !dependents.iter().any(|m| m == including_module) {
+            dependents.push(including_module.to_string());
+        }
+    }
+
+    /// Transitive closure of everything that depends on `changed`, including `changed` itself.
+    pub fn affected(&self, changed: &[String]) -> HashSet<String> {
+        let mut affected = HashSet::new();
+        let mut stack: Vec<String> = changed.to_vec();
+        while let Some(module) = stack.pop() {
+            if !affected.insert(module.clone()) {
+                continue;
+            }
+            if let Some(dependents) = self.dependents.get(&module) {
+                stack.extend(dependents.iter().cloned());
+            }
+        }
+        affected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_trie_maps_file_to_its_modules() {
+        let mut trie = FileTrie::new();
+        trie.insert("rtl/alu.v", "alu");
+        trie.insert("rtl/alu.v", "alu_core");
+        trie.insert("rtl/top.v", "top");
+
+        assert_eq!(trie.modules_at("rtl/alu.v"), &["alu", "alu_core"]);
+        assert_eq!(trie.modules_at("rtl/top.v"), &["top"]);
+        assert!(trie.modules_at("rtl/missing.v").is_empty());
+    }
+
+    #[test]
+    fn file_trie_insert_is_idempotent() {
+        let mut trie = FileTrie::new();
+        trie.insert("rtl/alu.v", "alu");
+        trie.insert("rtl/alu.v", "alu");
+        assert_eq!(trie.modules_at("rtl/alu.v"), &["alu"]);
+    }
+
+    #[test]
+    fn file_trie_lists_every_file_with_modules() {
+        let mut trie = FileTrie::new();
+        trie.insert("rtl/alu.v", "alu");
+        trie.insert("rtl/top.v", "top");
+
+        let mut paths = trie.file_paths();
+        paths.sort();
+        assert_eq!(paths, vec!["rtl/alu.v".to_string(), "rtl/top.v".to_string()]);
+    }
+
+    #[test]
+    fn reverse_dep_graph_propagates_through_diamond() {
+        // shared -> {left, right} -> top: a change to `shared` must affect `top`
+        // through both paths of the diamond, not just one.
+        let mut graph = ReverseDepGraph::new();
+        graph.add_edge("shared", "left");
+        graph.add_edge("shared", "right");
+        graph.add_edge("left", "top");
+        graph.add_edge("right", "top");
+
+        let affected = graph.affected(&["shared".to_string()]);
+        assert!(affected.contains("shared"));
+        assert!(affected.contains("left"));
+        assert!(affected.contains("right"));
+        assert!(affected.contains("top"));
+    }
+
+    #[test]
+    fn reverse_dep_graph_unrelated_module_unaffected() {
+        let mut graph = ReverseDepGraph::new();
+        graph.add_edge("shared", "top");
+        graph.add_edge("unrelated_dep", "unrelated_top");
+
+        let affected = graph.affected(&["shared".to_string()]);
+        assert!(!affected.contains("unrelated_dep"));
+        assert!(!affected.contains("unrelated_top"));
+    }
+}
@@ -0,0 +1,175 @@
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::fs::{read_to_string, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use toml_edit::{Array, DocumentMut, Item, Table, Value};
+
+/// The resolved, reproducible counterpart to `vpm.toml`. Where the manifest records a
+/// requested ref (branch/tag/rev/commit), the lock records exactly what was fetched for it.
+#[derive(Debug)]
+pub struct VpmLock {
+    lock_doc: DocumentMut,
+}
+
+impl VpmLock {
+    pub fn from(filepath: &str) -> Self {
+        if !Path::new(filepath).exists() {
+            let mut initial_doc = DocumentMut::new();
+            initial_doc["dependencies"] = Item::Table(Table::new());
+
+            let mut file = OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(filepath)
+                .expect("Failed to create vpm.lock");
+            file.write_all(initial_doc.to_string().as_bytes())
+                .expect("Failed to write to vpm.lock");
+        }
+
+        let lock_content = read_to_string(filepath).expect("Failed to read vpm.lock");
+        Self {
+            lock_doc: lock_content
+                .parse::<DocumentMut>()
+                .expect("Failed to parse vpm.lock"),
+        }
+    }
+
+    pub fn get_commit(&self, repo_link: &str) -> Option<&str> {
+        self.lock_doc["dependencies"][repo_link]["commit"].as_str()
+    }
+
+    pub fn get_top_modules(&self, repo_link: &str) -> Vec<String> {
+        self.lock_doc["dependencies"][repo_link]["top_modules"]
+            .as_array()
+            .map(|modules| {
+                modules
+                    .iter()
+                    .filter_map(|m| m.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn get_file_hash(&self, repo_link: &str, file_path: &str) -> Option<&str> {
+        self.lock_doc["dependencies"][repo_link]["files"][file_path].as_str()
+    }
+
+    pub fn set_package(
+        &mut self,
+        repo_link: &str,
+        commit: &str,
+        top_modules: &[String],
+        file_hashes: &[(String, String)],
+    ) {
+        let mut package = Table::new();
+        package["commit"] = Item::Value(Value::from(commit));
+        package["top_modules"] = Item::Value(Value::Array(
+            top_modules.iter().map(Value::from).collect::<Array>(),
+        ));
+
+        let mut files = Table::new();
+        for (file_path, hash) in file_hashes {
+            files[file_path] = Item::Value(Value::from(hash.as_str()));
+        }
+        package["files"] = Item::Table(files);
+
+        self.lock_doc["dependencies"][repo_link] = Item::Table(package);
+    }
+
+    pub fn remove_package(&mut self, repo_link: &str) {
+        if let Some(dependencies) = self.lock_doc["dependencies"].as_table_mut() {
+            dependencies.remove(repo_link);
+        }
+    }
+
+    /// Repo links from `dependencies` (the effective dependency set — a single manifest's,
+    /// or a workspace's deduplicated union) that are either missing from the lock or whose
+    /// `top_modules` no longer match what's locked — these need re-fetching.
+    pub fn stale_dependencies(&self, dependencies: &BTreeMap<String, Vec<String>>) -> Vec<String> {
+        dependencies
+            .iter()
+            .filter(|(repo_link, top_modules)| {
+                self.get_commit(repo_link).is_none_or(str::is_empty)
+                    || &self.get_top_modules(repo_link) != *top_modules
+            })
+            .map(|(repo_link, _)| repo_link.clone())
+            .collect()
+    }
+
+    pub fn write_to_file(&self, filepath: &str) -> Result<()> {
+        let lock_content = self.lock_doc.to_string();
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(filepath)
+            .expect("Failed to open vpm.lock");
+        file.write_all(lock_content.as_bytes())
+            .expect("Failed to write to vpm.lock");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_path(name: &str) -> String {
+        format!("{name}_{}.lock", std::process::id())
+    }
+
+    #[test]
+    fn set_package_round_trips_commit_modules_and_hashes() {
+        let path = scratch_path("set_package_round_trips");
+        let mut lock = VpmLock::from(&path);
+
+        lock.set_package(
+            "example.com/repo",
+            "abc123",
+            &["top".to_string()],
+            &[("src/top.v".to_string(), "deadbeef".to_string())],
+        );
+
+        assert_eq!(lock.get_commit("example.com/repo"), Some("abc123"));
+        assert_eq!(lock.get_top_modules("example.com/repo"), vec!["top".to_string()]);
+        assert_eq!(
+            lock.get_file_hash("example.com/repo", "src/top.v"),
+            Some("deadbeef")
+        );
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn stale_dependencies_flags_missing_and_changed_top_modules() {
+        let path = scratch_path("stale_dependencies");
+        let mut lock = VpmLock::from(&path);
+        lock.set_package("up-to-date", "abc123", &["top".to_string()], &[]);
+        lock.set_package("changed-modules", "def456", &["old".to_string()], &[]);
+
+        let mut dependencies = BTreeMap::new();
+        dependencies.insert("up-to-date".to_string(), vec!["top".to_string()]);
+        dependencies.insert("changed-modules".to_string(), vec!["new".to_string()]);
+        dependencies.insert("unlocked".to_string(), vec!["top".to_string()]);
+
+        let mut stale = lock.stale_dependencies(&dependencies);
+        stale.sort();
+        assert_eq!(stale, vec!["changed-modules".to_string(), "unlocked".to_string()]);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn remove_package_drops_the_locked_entry() {
+        let path = scratch_path("remove_package");
+        let mut lock = VpmLock::from(&path);
+        lock.set_package("gone", "abc123", &["top".to_string()], &[]);
+        lock.remove_package("gone");
+
+        assert_eq!(lock.get_commit("gone"), None);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
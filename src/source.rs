@@ -0,0 +1,145 @@
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::graph::{FileTrie, ReverseDepGraph};
+
+/// Discovers every `.v`/`.sv` source file under `root`.
+pub fn discover_files(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    walk(root, &mut files)?;
+    Ok(files)
+}
+
+fn walk(dir: &Path, out: &mut Vec<PathBuf>) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if path.file_name().and_then(|n| n.to_str()) != Some(".git") {
+                walk(&path, out)?;
+            }
+        } else if matches!(path.extension().and_then(|e| e.to_str()), Some("v" | "sv")) {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Scans `files` for `module <name>` declarations and `` `include "..." `` directives,
+/// building a file trie (file -> modules it defines) and the reverse dependency graph
+/// (included module -> modules that include it) from the real include relationships.
+pub fn build_graph(root: &Path, files: &[PathBuf]) -> Result<(FileTrie, ReverseDepGraph)> {
+    let mut trie = FileTrie::new();
+    let mut modules_by_file: Vec<(PathBuf, Vec<String>)> = Vec::new();
+
+    for file in files {
+        let contents = fs::read_to_string(file)?;
+        let modules = parse_module_names(&contents);
+        let relative = relative_path(root, file);
+        for module in &modules {
+            trie.insert(&relative, module);
+        }
+        modules_by_file.push((file.clone(), modules));
+    }
+
+    let mut graph = ReverseDepGraph::new();
+    for (file, including_modules) in &modules_by_file {
+        let contents = fs::read_to_string(file)?;
+        let parent = file.parent().unwrap_or(root);
+        for included in parse_includes(&contents) {
+            let included_path = parent.join(&included);
+            let Some((_, included_modules)) =
+                modules_by_file.iter().find(|(f, _)| *f == included_path)
+            else {
+                continue;
+            };
+            for included_module in included_modules {
+                for including_module in including_modules {
+                    graph.add_edge(included_module, including_module);
+                }
+            }
+        }
+    }
+
+    Ok((trie, graph))
+}
+
+fn relative_path(root: &Path, file: &Path) -> String {
+    file.strip_prefix(root)
+        .unwrap_or(file)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn parse_module_names(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let rest = line.trim().strip_prefix("module ")?;
+            rest.split(|c: char| c == '(' || c == ';' || c.is_whitespace())
+                .find(|s| !s.is_empty())
+                .map(str::to_string)
+        })
+        .collect()
+}
+
+fn parse_includes(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let rest = line.trim().strip_prefix("`include")?;
+            let quoted = rest.trim();
+            let inner = quoted.strip_prefix('"')?;
+            inner.split('"').next().map(str::to_string)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_module_names_finds_every_declaration() {
+        let contents = "module alpha (\n  input clk\n);\nendmodule\nmodule beta;\nendmodule\n";
+        assert_eq!(
+            parse_module_names(contents),
+            vec!["alpha".to_string(), "beta".to_string()]
+        );
+    }
+
+    #[test]
+    fn parse_includes_extracts_quoted_paths() {
+        let contents = "`include \"leaf.v\"\nmodule top;\nendmodule\n";
+        assert_eq!(parse_includes(contents), vec!["leaf.v".to_string()]);
+    }
+
+    #[test]
+    fn discover_files_finds_v_and_sv_and_skips_dot_git() {
+        let root = std::env::temp_dir().join(format!(
+            "vpm_source_discover_files_{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::create_dir_all(root.join("nested")).unwrap();
+        fs::write(root.join("top.v"), "module top;\nendmodule\n").unwrap();
+        fs::write(root.join("nested/leaf.sv"), "module leaf;\nendmodule\n").unwrap();
+        fs::write(root.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+        fs::write(root.join("README.md"), "not a source file\n").unwrap();
+
+        let mut files = discover_files(&root)
+            .unwrap()
+            .into_iter()
+            .map(|f| relative_path(&root, &f))
+            .collect::<Vec<_>>();
+        files.sort();
+
+        assert_eq!(files, vec!["nested/leaf.sv".to_string(), "top.v".to_string()]);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -1,10 +1,38 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
+use semver::VersionReq;
 use serde::{Deserialize, Serialize};
 use std::fs::{read_to_string, OpenOptions};
 use std::io::Write;
 use std::path::Path;
 use toml_edit::{Array, DocumentMut, InlineTable, Item, Table, Value};
 
+/// A git ref pin for a dependency, mirroring Cargo's `GitReference`.
+/// At most one of the three may be present on a dependency at a time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GitReference {
+    Branch(String),
+    Tag(String),
+    Rev(String),
+}
+
+impl GitReference {
+    fn key(&self) -> &'static str {
+        match self {
+            GitReference::Branch(_) => "branch",
+            GitReference::Tag(_) => "tag",
+            GitReference::Rev(_) => "rev",
+        }
+    }
+
+    fn spec(&self) -> &str {
+        match self {
+            GitReference::Branch(spec) | GitReference::Tag(spec) | GitReference::Rev(spec) => {
+                spec
+            }
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 struct Package {
     name: String,
@@ -15,7 +43,7 @@ struct Package {
 }
 
 #[derive(Debug)]
-struct VpmToml {
+pub(crate) struct VpmToml {
     toml_doc: DocumentMut,
 }
 
@@ -75,9 +103,28 @@ impl VpmToml {
         self.toml_doc["dependencies"].as_table()
     }
 
-    pub fn add_dependency(&mut self, git: &str, commit: Option<&str>) {
+    /// Sets the local alias (à la Cargo's renamed dependencies) a dependency is referenced under.
+    pub fn set_alias(&mut self, repo_link: &str, alias: &str) {
+        if let Some(dependency) = self.toml_doc["dependencies"][repo_link].as_inline_table_mut() {
+            dependency.insert("package", Value::from(alias));
+        }
+    }
+
+    pub fn add_dependency(
+        &mut self,
+        git: &str,
+        git_ref: Option<GitReference>,
+        version: Option<&VersionReq>,
+        commit: Option<&str>,
+    ) {
         let mut dependency = InlineTable::new();
         dependency.insert("top_modules", Value::Array(Array::new()));
+        if let Some(git_ref) = &git_ref {
+            dependency.insert(git_ref.key(), Value::from(git_ref.spec().to_string()));
+        }
+        if let Some(version) = version {
+            dependency.insert("version", Value::from(version.to_string()));
+        }
         dependency.insert(
             "commit",
             Value::from(commit.unwrap_or_default().to_string()),
@@ -85,6 +132,42 @@ impl VpmToml {
         self.toml_doc["dependencies"][git] = Item::Value(Value::InlineTable(dependency));
     }
 
+    /// Reads the `version` semver requirement recorded for a dependency, if any.
+    pub fn get_version_req(&self, repo_link: &str) -> Result<Option<VersionReq>> {
+        let Some(dependency) = self.toml_doc["dependencies"][repo_link].as_inline_table() else {
+            return Ok(None);
+        };
+        match dependency.get("version").and_then(Value::as_str) {
+            Some(version) => Ok(Some(VersionReq::parse(version)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Reads the `branch`/`tag`/`rev` spec recorded for a dependency, if any.
+    /// Errors if more than one ref kind is present, since they're mutually exclusive.
+    pub fn get_git_reference(&self, repo_link: &str) -> Result<Option<GitReference>> {
+        let Some(dependency) = self.toml_doc["dependencies"][repo_link].as_inline_table() else {
+            return Ok(None);
+        };
+        let branch = dependency.get("branch").and_then(Value::as_str);
+        let tag = dependency.get("tag").and_then(Value::as_str);
+        let rev = dependency.get("rev").and_then(Value::as_str);
+        match (branch, tag, rev) {
+            (Some(_), Some(_), _) | (Some(_), _, Some(_)) | (_, Some(_), Some(_)) => {
+                bail!("dependency `{repo_link}` specifies more than one of branch/tag/rev")
+            }
+            (Some(branch), None, None) => Ok(Some(GitReference::Branch(branch.to_string()))),
+            (None, Some(tag), None) => Ok(Some(GitReference::Tag(tag.to_string()))),
+            (None, None, Some(rev)) => Ok(Some(GitReference::Rev(rev.to_string()))),
+            (None, None, None) => Ok(None),
+        }
+    }
+
+    /// Records the commit a `GitReference` resolved to, keeping the original spec in place.
+    pub fn set_resolved_commit(&mut self, repo_link: &str, commit: &str) {
+        self.toml_doc["dependencies"][repo_link]["commit"] = Item::Value(Value::from(commit));
+    }
+
     pub fn add_top_module(&mut self, repo_link: &str, module_name: &str) {
         let array = self.toml_doc["dependencies"][repo_link]["top_modules"]
             .as_array_mut()
@@ -139,10 +222,15 @@ impl VpmToml {
     }
 }
 
-pub fn add_dependency(git: &str, commit: Option<&str>) -> Result<()> {
+pub fn add_dependency(
+    git: &str,
+    git_ref: Option<GitReference>,
+    version: Option<&VersionReq>,
+    commit: Option<&str>,
+) -> Result<()> {
     let mut vpm_toml = VpmToml::from("vpm.toml");
     if !vpm_toml.get_dependencies().unwrap().contains_key(git) {
-        vpm_toml.add_dependency(git, commit);
+        vpm_toml.add_dependency(git, git_ref, version, commit);
         vpm_toml.write_to_file("vpm.toml")?;
     }
     Ok(())
@@ -155,6 +243,13 @@ pub fn add_top_module(repo_link: &str, module_path: &str) -> Result<()> {
     Ok(())
 }
 
+pub fn set_dependency_alias(git: &str, alias: &str) -> Result<()> {
+    let mut vpm_toml = VpmToml::from("vpm.toml");
+    vpm_toml.set_alias(git, alias);
+    vpm_toml.write_to_file("vpm.toml")?;
+    Ok(())
+}
+
 #[allow(dead_code)]
 pub fn remove_dependency(git: &str) -> Result<()> {
     let mut vpm_toml = VpmToml::from("vpm.toml");
@@ -175,3 +270,59 @@ pub fn get_repo_links(module_name: &str) -> Vec<String> {
     let vpm_toml = VpmToml::from("vpm.toml");
     vpm_toml.get_repo_links(module_name)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_path(name: &str) -> String {
+        format!("{name}_{}.toml", std::process::id())
+    }
+
+    #[test]
+    fn get_git_reference_rejects_multiple_ref_kinds() {
+        let path = scratch_path("rejects_multiple_ref_kinds");
+        let mut vpm_toml = VpmToml::from(&path);
+        vpm_toml.add_dependency("example.com/repo", None, None, None);
+        // Directly poke both `branch` and `tag` onto the dependency to simulate a
+        // hand-edited manifest violating the mutual-exclusion invariant.
+        if let Some(dependency) = vpm_toml.toml_doc["dependencies"]["example.com/repo"].as_inline_table_mut() {
+            dependency.insert("branch", Value::from("main"));
+            dependency.insert("tag", Value::from("v1.0.0"));
+        }
+
+        assert!(vpm_toml.get_git_reference("example.com/repo").is_err());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn get_git_reference_returns_the_single_ref_kind_present() {
+        let path = scratch_path("returns_single_ref_kind");
+        let mut vpm_toml = VpmToml::from(&path);
+        vpm_toml.add_dependency(
+            "example.com/repo",
+            Some(GitReference::Branch("main".to_string())),
+            None,
+            None,
+        );
+
+        assert_eq!(
+            vpm_toml.get_git_reference("example.com/repo").unwrap(),
+            Some(GitReference::Branch("main".to_string()))
+        );
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn get_git_reference_is_none_when_unset() {
+        let path = scratch_path("is_none_when_unset");
+        let mut vpm_toml = VpmToml::from(&path);
+        vpm_toml.add_dependency("example.com/repo", None, None, None);
+
+        assert_eq!(vpm_toml.get_git_reference("example.com/repo").unwrap(), None);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
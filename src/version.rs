@@ -0,0 +1,76 @@
+use anyhow::{bail, Result};
+use semver::{Version, VersionReq};
+
+/// Parses `tags` (raw git tag names, e.g. `v1.2.3` or `1.2.3`) as semver versions and
+/// returns the highest one satisfying `req`, along with the original tag name to pin.
+/// Tags that aren't valid semver are ignored rather than treated as errors, since a repo
+/// may mix release tags with unrelated ones.
+pub fn resolve_version(tags: &[String], req: &VersionReq) -> Result<(String, Version)> {
+    let mut candidates: Vec<(String, Version)> = tags
+        .iter()
+        .filter_map(|tag| {
+            let version_str = tag.strip_prefix('v').unwrap_or(tag);
+            Version::parse(version_str)
+                .ok()
+                .map(|version| (tag.clone(), version))
+        })
+        .filter(|(_, version)| req.matches(version))
+        .collect();
+
+    candidates.sort_by(|a, b| a.1.cmp(&b.1));
+
+    match candidates.pop() {
+        Some(best) => Ok(best),
+        None if tags.is_empty() => {
+            bail!("no tags found for this repo; cannot resolve version requirement `{req}`")
+        }
+        None => bail!(
+            "no tag satisfies version requirement `{req}`; available tags: {}",
+            tags.join(", ")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn picks_highest_satisfying_tag() {
+        let (tag, version) = resolve_version(
+            &tags(&["v1.0.0", "v1.2.0", "v1.2.3", "v2.0.0"]),
+            &VersionReq::parse("^1.2").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(tag, "v1.2.3");
+        assert_eq!(version, Version::parse("1.2.3").unwrap());
+    }
+
+    #[test]
+    fn ignores_non_semver_tags() {
+        let (tag, _) = resolve_version(
+            &tags(&["release-candidate", "v1.0.0"]),
+            &VersionReq::parse("*").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(tag, "v1.0.0");
+    }
+
+    #[test]
+    fn errors_with_available_tags_when_nothing_matches() {
+        let err = resolve_version(&tags(&["v1.0.0", "v1.5.0"]), &VersionReq::parse("^2").unwrap())
+            .unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("v1.0.0"));
+        assert!(message.contains("v1.5.0"));
+    }
+
+    #[test]
+    fn errors_on_empty_tag_list() {
+        assert!(resolve_version(&[], &VersionReq::parse("^1").unwrap()).is_err());
+    }
+}
@@ -0,0 +1,253 @@
+use anyhow::{bail, Result};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item};
+
+use crate::toml::VpmToml;
+
+/// A multi-package project: a root `vpm.toml` carrying a `[workspace]` table (and, for a
+/// virtual manifest, no `[package]` of its own) whose `members` glob resolves to the
+/// sub-package manifests that make up the project.
+#[derive(Debug)]
+pub struct Workspace {
+    root: PathBuf,
+    members: Vec<PathBuf>,
+}
+
+impl Workspace {
+    /// Loads the workspace rooted at `root_manifest`, a `vpm.toml` with a `[workspace]` table.
+    pub fn from(root_manifest: &Path) -> Result<Self> {
+        let root = root_manifest
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .to_path_buf();
+
+        let content = std::fs::read_to_string(root_manifest)?;
+        let doc = content.parse::<DocumentMut>()?;
+
+        let Some(workspace) = doc.get("workspace").and_then(Item::as_table) else {
+            bail!("{} has no [workspace] table", root_manifest.display());
+        };
+
+        let patterns: Vec<String> = workspace
+            .get("members")
+            .and_then(Item::as_array)
+            .map(|members| {
+                members
+                    .iter()
+                    .filter_map(|m| m.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut members = Vec::new();
+        for pattern in patterns {
+            let glob_pattern = root.join(&pattern).join("vpm.toml");
+            for entry in glob::glob(&glob_pattern.to_string_lossy())? {
+                members.push(entry?);
+            }
+        }
+
+        Ok(Self { root, members })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn members(&self) -> &[PathBuf] {
+        &self.members
+    }
+
+    /// Loads every member manifest and merges their `dependencies`, deduplicating `top_modules`
+    /// per repo link so a module required by two members is only fetched once.
+    pub fn merged_dependencies(&self) -> BTreeMap<String, Vec<String>> {
+        let mut merged: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+        for member in &self.members {
+            let vpm_toml = VpmToml::from(&member.to_string_lossy());
+            let Some(dependencies) = vpm_toml.get_dependencies() else {
+                continue;
+            };
+
+            for (repo_link, dependency) in dependencies.iter() {
+                let top_modules: Vec<String> = dependency["top_modules"]
+                    .as_array()
+                    .map(|modules| {
+                        modules
+                            .iter()
+                            .filter_map(|m| m.as_str().map(str::to_string))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                let entry = merged.entry(repo_link.to_string()).or_default();
+                for module in top_modules {
+                    if !entry.contains(&module) {
+                        entry.push(module);
+                    }
+                }
+            }
+        }
+
+        merged
+    }
+
+    /// The member manifest that declares `repo_link`, if any.
+    pub fn manifest_for(&self, repo_link: &str) -> Option<PathBuf> {
+        self.members
+            .iter()
+            .find(|member| {
+                VpmToml::from(&member.to_string_lossy())
+                    .get_dependencies()
+                    .is_some_and(|dependencies| dependencies.contains_key(repo_link))
+            })
+            .cloned()
+    }
+}
+
+/// Walks up from `start_dir` looking for a `vpm.toml` with a `[workspace]` table,
+/// analogous to Cargo's workspace root discovery.
+pub fn find_workspace_root(start_dir: &Path) -> Option<PathBuf> {
+    let mut dir = Some(start_dir);
+    while let Some(current) = dir {
+        let candidate = current.join("vpm.toml");
+        if let Ok(content) = std::fs::read_to_string(&candidate) {
+            if let Ok(doc) = content.parse::<DocumentMut>() {
+                if doc.get("workspace").is_some() {
+                    return Some(candidate);
+                }
+            }
+        }
+        dir = current.parent();
+    }
+    None
+}
+
+/// The manifest that owns `repo_link` and should be resolved/written against: the workspace
+/// member that declares it if `start_dir` is inside a workspace (falling back to the
+/// workspace root if no member claims it), or the current directory's `vpm.toml` otherwise.
+/// `install` uses this so a resolved commit lands in the manifest that actually requested
+/// it, not whichever `vpm.toml` happens to be in the current directory.
+pub fn manifest_path_for(start_dir: &Path, repo_link: &str) -> Result<PathBuf> {
+    match find_workspace_root(start_dir) {
+        Some(root_manifest) => {
+            let workspace = Workspace::from(&root_manifest)?;
+            Ok(workspace
+                .manifest_for(repo_link)
+                .unwrap_or(root_manifest))
+        }
+        None => Ok(PathBuf::from("vpm.toml")),
+    }
+}
+
+/// The dependency set commands should operate over: if `start_dir` is inside a workspace,
+/// the deduplicated union of every member's dependencies; otherwise just the current
+/// directory's `vpm.toml`. Shared by `list`, `install`, `sim`, and `synth`.
+pub fn effective_dependencies(start_dir: &Path) -> Result<BTreeMap<String, Vec<String>>> {
+    if let Some(root_manifest) = find_workspace_root(start_dir) {
+        return Ok(Workspace::from(&root_manifest)?.merged_dependencies());
+    }
+
+    let mut dependencies = BTreeMap::new();
+    let vpm_toml = VpmToml::from("vpm.toml");
+    if let Some(deps) = vpm_toml.get_dependencies() {
+        for (repo_link, dependency) in deps.iter() {
+            let top_modules: Vec<String> = dependency["top_modules"]
+                .as_array()
+                .map(|modules| {
+                    modules
+                        .iter()
+                        .filter_map(|m| m.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default();
+            dependencies.insert(repo_link.to_string(), top_modules);
+        }
+    }
+    Ok(dependencies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Lays out a two-member workspace under a scratch directory and returns its root.
+    fn scratch_workspace(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("vpm_workspace_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("a")).unwrap();
+        std::fs::create_dir_all(root.join("b")).unwrap();
+
+        std::fs::write(
+            root.join("vpm.toml"),
+            "[workspace]\nmembers = [\"a\", \"b\"]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("a/vpm.toml"),
+            "[dependencies]\n\"example.com/shared\" = { top_modules = [\"top_a\"] }\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("b/vpm.toml"),
+            "[dependencies]\n\"example.com/shared\" = { top_modules = [\"top_b\"] }\n\"example.com/only_b\" = { top_modules = [\"top_only_b\"] }\n",
+        )
+        .unwrap();
+
+        root
+    }
+
+    #[test]
+    fn from_discovers_glob_members() {
+        let root = scratch_workspace("from_discovers_glob_members");
+        let workspace = Workspace::from(&root.join("vpm.toml")).unwrap();
+        assert_eq!(workspace.members().len(), 2);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn merged_dependencies_dedups_shared_repo_across_members() {
+        let root = scratch_workspace("merged_dependencies_dedups");
+        let workspace = Workspace::from(&root.join("vpm.toml")).unwrap();
+        let merged = workspace.merged_dependencies();
+
+        assert_eq!(merged.len(), 2);
+        let mut shared_modules = merged["example.com/shared"].clone();
+        shared_modules.sort();
+        assert_eq!(shared_modules, vec!["top_a".to_string(), "top_b".to_string()]);
+        assert_eq!(merged["example.com/only_b"], vec!["top_only_b".to_string()]);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn manifest_for_finds_owning_member() {
+        let root = scratch_workspace("manifest_for_finds_owning_member");
+        let workspace = Workspace::from(&root.join("vpm.toml")).unwrap();
+
+        assert_eq!(
+            workspace.manifest_for("example.com/only_b"),
+            Some(root.join("b/vpm.toml"))
+        );
+        assert_eq!(workspace.manifest_for("example.com/nonexistent"), None);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn find_workspace_root_walks_up_from_a_nested_member_dir() {
+        let root = scratch_workspace("find_workspace_root_walks_up");
+        let found = find_workspace_root(&root.join("a"));
+        assert_eq!(found, Some(root.join("vpm.toml")));
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn manifest_path_for_prefers_the_declaring_member_over_the_workspace_root() {
+        let root = scratch_workspace("manifest_path_for_prefers_member");
+        let manifest = manifest_path_for(&root.join("a"), "example.com/only_b").unwrap();
+        assert_eq!(manifest, root.join("b/vpm.toml"));
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}